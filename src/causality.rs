@@ -0,0 +1,15 @@
+/// Opaque version marker returned alongside a value from [`crate::db::DBOps::get`]
+/// and accepted by [`crate::db::DBOps::write_if`]. Callers thread it through a
+/// read-modify-write loop without ever constructing or inspecting it
+/// themselves; today it is backed by the manifest's ETag, which makes it
+/// DB-wide rather than per-key: `write_if` on one key spuriously conflicts
+/// with a concurrent write to any other key, not just the same one. See
+/// the limitation noted on [`crate::db::DB`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CausalityToken(pub(crate) String);
+
+impl CausalityToken {
+    pub(crate) fn new(etag: impl Into<String>) -> Self {
+        CausalityToken(etag.into())
+    }
+}
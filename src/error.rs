@@ -0,0 +1,55 @@
+use crate::object_store::ObjectStoreError;
+use crate::sst::file::writer::WriterError;
+use crate::write_batch::DecodeError;
+
+/// Errors surfaced by [`crate::db::DBOps`].
+#[derive(Debug)]
+pub enum DbError {
+    NotFound,
+    /// A [`crate::db::DBOps::write_if`] call's expected causality token no
+    /// longer matched the current state.
+    Conflict,
+    Store(ObjectStoreError),
+    /// The segment a read or write operated on failed its checksum, or
+    /// its encoded ops couldn't be parsed back — the object is truncated
+    /// or corrupted rather than simply unreadable.
+    Corrupt(String),
+}
+
+impl From<ObjectStoreError> for DbError {
+    fn from(err: ObjectStoreError) -> Self {
+        match err {
+            ObjectStoreError::PreconditionFailed(_) => DbError::Conflict,
+            ObjectStoreError::NotFound(_) => DbError::NotFound,
+            other => DbError::Store(other),
+        }
+    }
+}
+
+impl From<WriterError> for DbError {
+    fn from(err: WriterError) -> Self {
+        match err {
+            WriterError::Store(e) => DbError::from(e),
+            other => DbError::Corrupt(other.to_string()),
+        }
+    }
+}
+
+impl From<DecodeError> for DbError {
+    fn from(err: DecodeError) -> Self {
+        DbError::Corrupt(err.to_string())
+    }
+}
+
+impl std::fmt::Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DbError::NotFound => write!(f, "key not found"),
+            DbError::Conflict => write!(f, "causality token conflict"),
+            DbError::Store(e) => write!(f, "store error: {e}"),
+            DbError::Corrupt(msg) => write!(f, "segment corrupt: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for DbError {}
@@ -0,0 +1,10 @@
+pub mod causality;
+pub mod db;
+pub mod error;
+pub mod manifest;
+pub mod object_store;
+pub mod sst;
+pub mod storage_config;
+pub mod storage_registry;
+pub mod subspace;
+pub mod write_batch;
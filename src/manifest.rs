@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::object_store::{ObjectStore, ObjectStoreError};
+
+/// Well-known path readers and writers use to agree on the current
+/// segment. There is exactly one of these per `DB`.
+pub const MANIFEST_PATH: &str = "MANIFEST";
+
+/// Points readers at the current segment. A writer publishes a batch by
+/// writing the new segment first, then flipping this pointer with a
+/// single `put`; a crash before the flip leaves the previous segment as
+/// the only visible state, so readers never see a partially-written
+/// batch.
+pub struct Manifest<S: ObjectStore> {
+    store: Arc<S>,
+}
+
+impl<S: ObjectStore> Manifest<S> {
+    pub fn new(store: Arc<S>) -> Self {
+        Manifest { store }
+    }
+
+    /// Returns the path of the currently published segment, or `None` if
+    /// no batch has been committed yet.
+    pub async fn current_segment(&self) -> Result<Option<String>, ObjectStoreError> {
+        match self.store.get(MANIFEST_PATH).await {
+            Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+            Err(ObjectStoreError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Like [`Manifest::current_segment`], but also returns the
+    /// manifest's current ETag, which doubles as the DB-wide causality
+    /// token handed back by `DBOps::get`.
+    pub async fn current_with_etag(&self) -> Result<Option<(String, String)>, ObjectStoreError> {
+        match self.store.get_with_etag(MANIFEST_PATH).await {
+            Ok((bytes, etag)) => Ok(Some((String::from_utf8_lossy(&bytes).into_owned(), etag))),
+            Err(ObjectStoreError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Unconditionally publishes `segment_path` as the current segment.
+    /// Readers only observe the new segment once this call returns
+    /// successfully.
+    pub async fn publish(&self, segment_path: &str) -> Result<(), ObjectStoreError> {
+        self.store
+            .put(MANIFEST_PATH, Bytes::copy_from_slice(segment_path.as_bytes()))
+            .await
+    }
+
+    /// Publishes `segment_path` only if the manifest's ETag still matches
+    /// `expected_etag` (`None` meaning "no manifest published yet"),
+    /// returning the new ETag. Fails with
+    /// [`ObjectStoreError::PreconditionFailed`] if another writer beat us
+    /// to it, so callers can surface a conflict instead of clobbering
+    /// their write.
+    pub async fn publish_if(
+        &self,
+        segment_path: &str,
+        expected_etag: Option<&str>,
+    ) -> Result<String, ObjectStoreError> {
+        self.store
+            .put_if_match(
+                MANIFEST_PATH,
+                Bytes::copy_from_slice(segment_path.as_bytes()),
+                expected_etag,
+            )
+            .await
+    }
+}
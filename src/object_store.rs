@@ -0,0 +1,782 @@
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::io::ReaderStream;
+
+/// Default part size for S3 multipart uploads driven off a streaming
+/// reader. Chosen to stay above S3's 5 MiB minimum part size while keeping
+/// memory bounded per in-flight part.
+const MULTIPART_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Pseudo-ETag for backends (local filesystem, in-memory) that have no
+/// native object versioning of their own: a content hash stands in for
+/// what S3 would hand back as the real ETag.
+fn content_etag(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Errors surfaced by an [`ObjectStore`] implementation.
+#[derive(Debug)]
+pub enum ObjectStoreError {
+    NotFound(String),
+    Io(String),
+    Backend(String),
+    /// A conditional write's `expected_etag` no longer matched the stored
+    /// object (S3 If-Match/If-None-Match semantics).
+    PreconditionFailed(String),
+}
+
+impl std::fmt::Display for ObjectStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectStoreError::NotFound(path) => write!(f, "object not found: {path}"),
+            ObjectStoreError::Io(msg) => write!(f, "io error: {msg}"),
+            ObjectStoreError::Backend(msg) => write!(f, "backend error: {msg}"),
+            ObjectStoreError::PreconditionFailed(path) => {
+                write!(f, "precondition failed: {path}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ObjectStoreError {}
+
+/// Backend-agnostic access to the blob store `Writer` and the SSTable layer
+/// read and write against. Implementations exist for S3, the local
+/// filesystem, and an in-memory map for tests, so the rest of the crate
+/// never depends on `aws_sdk_s3` directly.
+pub trait ObjectStore: Send + Sync {
+    fn put(
+        &self,
+        path: &str,
+        bytes: Bytes,
+    ) -> impl Future<Output = Result<(), ObjectStoreError>> + Send;
+
+    fn get(&self, path: &str) -> impl Future<Output = Result<Bytes, ObjectStoreError>> + Send;
+
+    /// Like [`ObjectStore::get`], but also returns the object's current
+    /// ETag (or an equivalent opaque version marker) for use with
+    /// [`ObjectStore::put_if_match`].
+    fn get_with_etag(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<(Bytes, String), ObjectStoreError>> + Send;
+
+    fn list(
+        &self,
+        prefix: &str,
+    ) -> impl Future<Output = Result<Vec<String>, ObjectStoreError>> + Send;
+
+    fn delete(&self, path: &str) -> impl Future<Output = Result<(), ObjectStoreError>> + Send;
+
+    fn copy(
+        &self,
+        src: &str,
+        dst: &str,
+    ) -> impl Future<Output = Result<(), ObjectStoreError>> + Send;
+
+    /// Uploads `reader` to `path` without materializing the whole object in
+    /// memory. Backends that support multipart uploads (e.g. S3) chunk the
+    /// reader into parts as they arrive.
+    fn put_streaming(
+        &self,
+        path: &str,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> impl Future<Output = Result<(), ObjectStoreError>> + Send;
+
+    /// Returns the object at `path` as a stream of chunks instead of a
+    /// single `Bytes` buffer, so large values can be copied with bounded
+    /// memory.
+    fn get_streaming(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError>>
+           + Send;
+
+    /// Writes `bytes` to `path` only if the object's current ETag matches
+    /// `expected_etag` (or, when `expected_etag` is `None`, only if the
+    /// object does not exist yet). Returns the new ETag on success, or
+    /// [`ObjectStoreError::PreconditionFailed`] if the compare fails, so
+    /// concurrent writers on object storage never silently clobber each
+    /// other. Backed by S3's If-Match/If-None-Match headers where the
+    /// backend supports them.
+    fn put_if_match(
+        &self,
+        path: &str,
+        bytes: Bytes,
+        expected_etag: Option<&str>,
+    ) -> impl Future<Output = Result<String, ObjectStoreError>> + Send;
+}
+
+/// Wraps today's `aws_sdk_s3::Client` so S3 stays a normal `ObjectStore`
+/// backend rather than something `Writer` and the SSTable layer talk to
+/// directly.
+pub struct S3ObjectStore {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl S3ObjectStore {
+    pub fn new(client: aws_sdk_s3::Client, bucket: impl Into<String>) -> Self {
+        S3ObjectStore {
+            client,
+            bucket: bucket.into(),
+        }
+    }
+
+    pub async fn from_env(bucket: impl Into<String>) -> Self {
+        let client = aws_sdk_s3::Client::new(&aws_config::load_from_env().await);
+        S3ObjectStore::new(client, bucket)
+    }
+
+    /// Builds a client bound to `config`'s endpoint, region, and
+    /// credentials instead of always reading them from the environment,
+    /// so a process can talk to several buckets or S3-compatible
+    /// endpoints (prod S3, a MinIO dev box, a GCS-over-S3 gateway) at
+    /// once.
+    pub async fn from_config(config: &crate::storage_config::StorageConfig) -> Self {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_sdk_s3::config::Region::new(config.region.clone()));
+
+        if let Some(endpoint_url) = &config.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url.clone());
+        }
+        if let Some(creds) = &config.credentials {
+            loader = loader.credentials_provider(aws_sdk_s3::config::Credentials::new(
+                creds.access_key_id.clone(),
+                creds.secret_access_key.clone(),
+                creds.session_token.clone(),
+                None,
+                "storage-config",
+            ));
+        }
+
+        let sdk_config = loader.load().await;
+        let mut s3_config = aws_sdk_s3::config::Builder::from(&sdk_config);
+        if config.force_path_style {
+            s3_config = s3_config.force_path_style(true);
+        }
+
+        S3ObjectStore::new(aws_sdk_s3::Client::from_conf(s3_config.build()), &config.bucket)
+    }
+}
+
+impl ObjectStore for S3ObjectStore {
+    async fn put(&self, path: &str, bytes: Bytes) -> Result<(), ObjectStoreError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .body(bytes.into())
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes, ObjectStoreError> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error().is_some_and(|se| se.is_no_such_key()) {
+                    ObjectStoreError::NotFound(path.to_string())
+                } else {
+                    ObjectStoreError::Backend(e.to_string())
+                }
+            })?;
+
+        let body = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        Ok(body.into_bytes())
+    }
+
+    async fn get_with_etag(&self, path: &str) -> Result<(Bytes, String), ObjectStoreError> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error().is_some_and(|se| se.is_no_such_key()) {
+                    ObjectStoreError::NotFound(path.to_string())
+                } else {
+                    ObjectStoreError::Backend(e.to_string())
+                }
+            })?;
+
+        let etag = output.e_tag().unwrap_or_default().to_string();
+        let body = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        Ok((body.into_bytes(), etag))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, ObjectStoreError> {
+        let output = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(prefix)
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+
+        Ok(output
+            .contents()
+            .iter()
+            .filter_map(|obj| obj.key().map(str::to_owned))
+            .collect())
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), ObjectStoreError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn copy(&self, src: &str, dst: &str) -> Result<(), ObjectStoreError> {
+        let copy_source = format!("{}/{}", self.bucket, src);
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(copy_source)
+            .key(dst)
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn put_streaming(
+        &self,
+        path: &str,
+        mut reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> Result<(), ObjectStoreError> {
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+        let upload_id = upload
+            .upload_id()
+            .ok_or_else(|| ObjectStoreError::Backend("missing upload id".to_string()))?;
+
+        let mut completed_parts = Vec::new();
+        let mut part_number = 1;
+
+        loop {
+            let mut buf = BytesMut::zeroed(MULTIPART_PART_SIZE);
+            let mut filled = 0;
+            while filled < buf.len() {
+                let n = reader
+                    .read(&mut buf[filled..])
+                    .await
+                    .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+                if n == 0 {
+                    break;
+                }
+                filled += n;
+            }
+            if filled == 0 {
+                break;
+            }
+            buf.truncate(filled);
+
+            let part = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(path)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(buf.freeze().into())
+                .send()
+                .await
+                .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+
+            completed_parts.push(
+                aws_sdk_s3::types::CompletedPart::builder()
+                    .e_tag(part.e_tag().unwrap_or_default())
+                    .part_number(part_number)
+                    .build(),
+            );
+            part_number += 1;
+
+            if filled < MULTIPART_PART_SIZE {
+                break;
+            }
+        }
+
+        if completed_parts.is_empty() {
+            // An empty reader never filled a part above, and S3 rejects
+            // `complete_multipart_upload` with zero parts. Abort the
+            // upload and fall back to a plain `put` of the empty body.
+            self.client
+                .abort_multipart_upload()
+                .bucket(&self.bucket)
+                .key(path)
+                .upload_id(upload_id)
+                .send()
+                .await
+                .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+            return self.put(path, Bytes::new()).await;
+        }
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(path)
+            .upload_id(upload_id)
+            .multipart_upload(
+                aws_sdk_s3::types::CompletedMultipartUpload::builder()
+                    .set_parts(Some(completed_parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get_streaming(
+        &self,
+        path: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Backend(e.to_string()))?;
+
+        Ok(ReaderStream::new(output.body.into_async_read())
+            .map(|chunk| chunk.map_err(|e| ObjectStoreError::Io(e.to_string())))
+            .boxed())
+    }
+
+    async fn put_if_match(
+        &self,
+        path: &str,
+        bytes: Bytes,
+        expected_etag: Option<&str>,
+    ) -> Result<String, ObjectStoreError> {
+        let mut request = self.client.put_object().bucket(&self.bucket).key(path).body(bytes.into());
+        request = match expected_etag {
+            Some(etag) => request.if_match(etag),
+            None => request.if_none_match("*"),
+        };
+
+        let output = request.send().await.map_err(|e| {
+            // S3 rejects a failed If-Match/If-None-Match with HTTP 412
+            // regardless of how the SDK renders the error's `Display` text,
+            // so check the response status rather than string-matching it.
+            if e.raw_response().is_some_and(|r| r.status().as_u16() == 412) {
+                ObjectStoreError::PreconditionFailed(path.to_string())
+            } else {
+                ObjectStoreError::Backend(e.to_string())
+            }
+        })?;
+
+        Ok(output.e_tag().unwrap_or_default().to_string())
+    }
+}
+
+/// An `ObjectStore` backed by a directory on the local filesystem, rooted
+/// at `root`. Useful for running against MinIO-free dev setups.
+pub struct LocalFsObjectStore {
+    root: PathBuf,
+}
+
+impl LocalFsObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalFsObjectStore { root: root.into() }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+impl ObjectStore for LocalFsObjectStore {
+    async fn put(&self, path: &str, bytes: Bytes) -> Result<(), ObjectStoreError> {
+        let full_path = self.resolve(path);
+        if let Some(parent) = full_path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        }
+        tokio::fs::write(&full_path, bytes)
+            .await
+            .map_err(|e| ObjectStoreError::Io(e.to_string()))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes, ObjectStoreError> {
+        let full_path = self.resolve(path);
+        match tokio::fs::read(&full_path).await {
+            Ok(data) => Ok(Bytes::from(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ObjectStoreError::NotFound(path.to_string()))
+            }
+            Err(e) => Err(ObjectStoreError::Io(e.to_string())),
+        }
+    }
+
+    async fn get_with_etag(&self, path: &str) -> Result<(Bytes, String), ObjectStoreError> {
+        let bytes = self.get(path).await?;
+        let etag = content_etag(&bytes);
+        Ok((bytes, etag))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, ObjectStoreError> {
+        let mut paths = Vec::new();
+        let mut stack = vec![self.root.clone()];
+
+        while let Some(dir) = stack.pop() {
+            let mut entries = tokio::fs::read_dir(&dir)
+                .await
+                .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| ObjectStoreError::Io(e.to_string()))?
+            {
+                let path = entry.path();
+                if path.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+                if let Ok(relative) = path.strip_prefix(&self.root) {
+                    let key = relative.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/");
+                    if key.starts_with(prefix) {
+                        paths.push(key);
+                    }
+                }
+            }
+        }
+
+        paths.sort();
+        Ok(paths)
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), ObjectStoreError> {
+        let full_path = self.resolve(path);
+        match tokio::fs::remove_file(&full_path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ObjectStoreError::NotFound(path.to_string()))
+            }
+            Err(e) => Err(ObjectStoreError::Io(e.to_string())),
+        }
+    }
+
+    async fn copy(&self, src: &str, dst: &str) -> Result<(), ObjectStoreError> {
+        let src_path = self.resolve(src);
+        let dst_path = self.resolve(dst);
+        if let Some(parent) = dst_path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        }
+        match tokio::fs::copy(&src_path, &dst_path).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ObjectStoreError::NotFound(src.to_string()))
+            }
+            Err(e) => Err(ObjectStoreError::Io(e.to_string())),
+        }
+    }
+
+    async fn put_streaming(
+        &self,
+        path: &str,
+        mut reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> Result<(), ObjectStoreError> {
+        let full_path = self.resolve(path);
+        if let Some(parent) = full_path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        }
+        let mut file = tokio::fs::File::create(&full_path)
+            .await
+            .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        tokio::io::copy(&mut reader, &mut file)
+            .await
+            .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_streaming(
+        &self,
+        path: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError> {
+        let full_path = self.resolve(path);
+        let file = match tokio::fs::File::open(&full_path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(ObjectStoreError::NotFound(path.to_string()))
+            }
+            Err(e) => return Err(ObjectStoreError::Io(e.to_string())),
+        };
+
+        Ok(ReaderStream::new(file)
+            .map(|chunk| chunk.map_err(|e| ObjectStoreError::Io(e.to_string())))
+            .boxed())
+    }
+
+    /// Best-effort compare-and-swap: the local filesystem has no atomic
+    /// conditional write, so this checks then writes without holding a
+    /// lock across the two steps. Fine for the single-process dev setups
+    /// this backend targets; S3 is what gives real atomicity.
+    async fn put_if_match(
+        &self,
+        path: &str,
+        bytes: Bytes,
+        expected_etag: Option<&str>,
+    ) -> Result<String, ObjectStoreError> {
+        let current = self.get(path).await;
+        match (expected_etag, &current) {
+            (Some(expected), Ok(existing)) if content_etag(existing) != expected => {
+                return Err(ObjectStoreError::PreconditionFailed(path.to_string()));
+            }
+            (Some(_), Err(ObjectStoreError::NotFound(_))) => {
+                return Err(ObjectStoreError::PreconditionFailed(path.to_string()));
+            }
+            (None, Ok(_)) => {
+                return Err(ObjectStoreError::PreconditionFailed(path.to_string()));
+            }
+            (_, Err(e)) if !matches!(e, ObjectStoreError::NotFound(_)) => {
+                return Err(ObjectStoreError::Io(e.to_string()));
+            }
+            _ => {}
+        }
+
+        let new_etag = content_etag(&bytes);
+        self.put(path, bytes).await?;
+        Ok(new_etag)
+    }
+}
+
+/// An in-memory `ObjectStore` for unit tests that otherwise exercise the
+/// `Writer`/SSTable code paths without reaching the network.
+#[derive(Default)]
+pub struct MemoryObjectStore {
+    objects: Mutex<BTreeMap<String, Bytes>>,
+}
+
+impl MemoryObjectStore {
+    pub fn new() -> Self {
+        MemoryObjectStore::default()
+    }
+}
+
+impl ObjectStore for MemoryObjectStore {
+    async fn put(&self, path: &str, bytes: Bytes) -> Result<(), ObjectStoreError> {
+        self.objects.lock().unwrap().insert(path.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes, ObjectStoreError> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| ObjectStoreError::NotFound(path.to_string()))
+    }
+
+    async fn get_with_etag(&self, path: &str) -> Result<(Bytes, String), ObjectStoreError> {
+        let bytes = self.get(path).await?;
+        let etag = content_etag(&bytes);
+        Ok((bytes, etag))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, ObjectStoreError> {
+        Ok(self
+            .objects
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), ObjectStoreError> {
+        self.objects
+            .lock()
+            .unwrap()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| ObjectStoreError::NotFound(path.to_string()))
+    }
+
+    async fn copy(&self, src: &str, dst: &str) -> Result<(), ObjectStoreError> {
+        let bytes = self
+            .objects
+            .lock()
+            .unwrap()
+            .get(src)
+            .cloned()
+            .ok_or_else(|| ObjectStoreError::NotFound(src.to_string()))?;
+        self.objects.lock().unwrap().insert(dst.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn put_streaming(
+        &self,
+        path: &str,
+        mut reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> Result<(), ObjectStoreError> {
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .map_err(|e| ObjectStoreError::Io(e.to_string()))?;
+        self.put(path, Bytes::from(buf)).await
+    }
+
+    async fn get_streaming(
+        &self,
+        path: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError> {
+        let bytes = self.get(path).await?;
+        Ok(stream::once(async move { Ok(bytes) }).boxed())
+    }
+
+    async fn put_if_match(
+        &self,
+        path: &str,
+        bytes: Bytes,
+        expected_etag: Option<&str>,
+    ) -> Result<String, ObjectStoreError> {
+        let mut objects = self.objects.lock().unwrap();
+        match (expected_etag, objects.get(path)) {
+            (Some(expected), Some(existing)) if content_etag(existing) != expected => {
+                return Err(ObjectStoreError::PreconditionFailed(path.to_string()));
+            }
+            (Some(_), None) => return Err(ObjectStoreError::PreconditionFailed(path.to_string())),
+            (None, Some(_)) => return Err(ObjectStoreError::PreconditionFailed(path.to_string())),
+            _ => {}
+        }
+
+        let new_etag = content_etag(&bytes);
+        objects.insert(path.to_string(), bytes);
+        Ok(new_etag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn memory_store_put_get_delete() {
+        let store = MemoryObjectStore::new();
+        store.put("a/b", Bytes::from_static(b"hello")).await.unwrap();
+        assert_eq!(store.get("a/b").await.unwrap(), Bytes::from_static(b"hello"));
+
+        let listed = store.list("a/").await.unwrap();
+        assert_eq!(listed, vec!["a/b".to_string()]);
+
+        store.delete("a/b").await.unwrap();
+        assert!(matches!(store.get("a/b").await, Err(ObjectStoreError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn memory_store_copy() {
+        let store = MemoryObjectStore::new();
+        store.put("src", Bytes::from_static(b"data")).await.unwrap();
+        store.copy("src", "dst").await.unwrap();
+        assert_eq!(store.get("dst").await.unwrap(), Bytes::from_static(b"data"));
+    }
+
+    #[tokio::test]
+    async fn memory_store_streaming_roundtrip() {
+        let store = MemoryObjectStore::new();
+        let reader = std::io::Cursor::new(b"streamed value".to_vec());
+        store.put_streaming("big", reader).await.unwrap();
+
+        let mut stream = store.get_streaming("big").await.unwrap();
+        let mut collected = BytesMut::new();
+        while let Some(chunk) = stream.next().await {
+            collected.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(collected.freeze(), Bytes::from_static(b"streamed value"));
+    }
+
+    #[tokio::test]
+    async fn put_if_match_rejects_stale_etag() {
+        let store = MemoryObjectStore::new();
+        store.put("k", Bytes::from_static(b"v1")).await.unwrap();
+        let (_, etag) = store.get_with_etag("k").await.unwrap();
+
+        store.put("k", Bytes::from_static(b"v2")).await.unwrap();
+        let err = store
+            .put_if_match("k", Bytes::from_static(b"v3"), Some(&etag))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::PreconditionFailed(_)));
+    }
+
+    #[tokio::test]
+    async fn put_if_match_accepts_current_etag() {
+        let store = MemoryObjectStore::new();
+        store.put("k", Bytes::from_static(b"v1")).await.unwrap();
+        let (_, etag) = store.get_with_etag("k").await.unwrap();
+
+        store
+            .put_if_match("k", Bytes::from_static(b"v2"), Some(&etag))
+            .await
+            .unwrap();
+        assert_eq!(store.get("k").await.unwrap(), Bytes::from_static(b"v2"));
+    }
+
+    #[tokio::test]
+    async fn put_if_match_none_requires_absence() {
+        let store = MemoryObjectStore::new();
+        store
+            .put_if_match("k", Bytes::from_static(b"v1"), None)
+            .await
+            .unwrap();
+
+        let err = store
+            .put_if_match("k", Bytes::from_static(b"v2"), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::PreconditionFailed(_)));
+    }
+}
@@ -1,13 +1,254 @@
-pub struct Writer {
-  s3_client: aws_sdk_s3::Client,
+use std::sync::Arc;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use crc::{Crc, CRC_32_ISCSI};
+use futures::stream::BoxStream;
+use tokio::io::AsyncRead;
+
+use crate::object_store::{ObjectStore, ObjectStoreError};
+
+/// Segment blocks are checksummed individually at this granularity so a
+/// single corrupted block doesn't force re-reading (or failing) the whole
+/// object.
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Bumped whenever the footer layout changes; readers reject footers from
+/// a version they don't understand instead of misparsing them.
+const FOOTER_VERSION: u8 = 1;
+
+/// Fixed-size trailer present in every footer: footer CRC (4 bytes),
+/// version (1 byte), block count (4 bytes). The per-block CRCs come
+/// before this and are variable length, so readers parse the footer from
+/// the end inward.
+const FOOTER_TRAILER_LEN: usize = 4 + 1 + 4;
+
+const CRC32C: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+#[derive(Debug)]
+pub enum WriterError {
+    Store(ObjectStoreError),
+    /// The block at `block_index` didn't match its stored CRC32C,
+    /// indicating truncation or corruption on the object store backend.
+    ChecksumMismatch {
+        block_index: usize,
+    },
+    /// The footer itself failed its own trailing CRC check.
+    FooterCorrupt,
+    UnsupportedFooterVersion(u8),
+}
+
+impl std::fmt::Display for WriterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriterError::Store(e) => write!(f, "object store error: {e}"),
+            WriterError::ChecksumMismatch { block_index } => {
+                write!(f, "checksum mismatch in block {block_index}")
+            }
+            WriterError::FooterCorrupt => write!(f, "segment footer failed its checksum"),
+            WriterError::UnsupportedFooterVersion(v) => {
+                write!(f, "unsupported segment footer version {v}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WriterError {}
+
+impl From<ObjectStoreError> for WriterError {
+    fn from(e: ObjectStoreError) -> Self {
+        WriterError::Store(e)
+    }
 }
 
-impl Writer {
-  pub async fn new() -> Self {
-    let writer  = Writer {
-      s3_client: aws_sdk_s3::Client::new(&aws_config::load_from_env().await)
-    };
+/// Tunables for how a [`Writer`] reads back the segments it writes.
+pub struct WriterConfig {
+    /// When `true` (the default), every block's CRC32C is checked on read.
+    /// Latency-sensitive callers that trust the backend can disable this.
+    pub verify_checksums: bool,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        WriterConfig {
+            verify_checksums: true,
+        }
+    }
+}
+
+pub struct Writer<S: ObjectStore> {
+    store: Arc<S>,
+    config: WriterConfig,
+}
+
+impl<S: ObjectStore> Writer<S> {
+    pub fn new(store: Arc<S>) -> Self {
+        Writer::with_config(store, WriterConfig::default())
+    }
+
+    pub fn with_config(store: Arc<S>, config: WriterConfig) -> Self {
+        Writer { store, config }
+    }
+
+    /// Writes `data` to `path` as a checksummed segment: `data` is split
+    /// into fixed-size blocks, each with its own CRC32C, followed by a
+    /// versioned footer (block count, per-block CRCs, and a trailing CRC
+    /// over the footer itself) so reads can detect corruption from
+    /// truncated uploads or bit-rot on the backend.
+    pub async fn write_segment(&self, path: &str, data: &[u8]) -> Result<(), WriterError> {
+        let mut block_crcs = Vec::new();
+        for block in data.chunks(BLOCK_SIZE) {
+            block_crcs.push(CRC32C.checksum(block));
+        }
+
+        let mut footer = BytesMut::new();
+        for crc in &block_crcs {
+            footer.put_u32(*crc);
+        }
+        footer.put_u32(block_crcs.len() as u32);
+        footer.put_u8(FOOTER_VERSION);
+        let footer_crc = CRC32C.checksum(&footer);
+        footer.put_u32(footer_crc);
+
+        let mut object = BytesMut::with_capacity(data.len() + footer.len());
+        object.put_slice(data);
+        object.put(footer);
+
+        self.store.put(path, object.freeze()).await?;
+        Ok(())
+    }
+
+    /// Reads back a segment written with [`Writer::write_segment`],
+    /// verifying block checksums unless disabled via [`WriterConfig`].
+    pub async fn read_segment(&self, path: &str) -> Result<Bytes, WriterError> {
+        let object = self.store.get(path).await?;
+        decode_segment(object, self.config.verify_checksums)
+    }
+
+    /// Uploads `reader` to `path` in bounded memory, chunking it into parts
+    /// as the backend allows (e.g. S3 multipart upload) instead of
+    /// materializing the whole object first.
+    pub async fn write_streaming(
+        &self,
+        path: &str,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> Result<(), ObjectStoreError> {
+        self.store.put_streaming(path, reader).await
+    }
+
+    /// Reads the object at `path` back as a stream of chunks instead of a
+    /// single `Bytes` buffer.
+    pub async fn read_streaming(
+        &self,
+        path: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError> {
+        self.store.get_streaming(path).await
+    }
+}
+
+fn decode_segment(object: Bytes, verify: bool) -> Result<Bytes, WriterError> {
+    if object.len() < FOOTER_TRAILER_LEN {
+        // Too small to even hold the fixed trailer: truncated, not a
+        // version we don't understand.
+        return Err(WriterError::FooterCorrupt);
+    }
+
+    let len = object.len();
+    let stored_footer_crc = u32::from_be_bytes(object[len - 4..len].try_into().unwrap());
+    let version = object[len - 5];
+    if version != FOOTER_VERSION {
+        return Err(WriterError::UnsupportedFooterVersion(version));
+    }
+    let block_count = u32::from_be_bytes(object[len - 9..len - 5].try_into().unwrap()) as usize;
+
+    let footer_len = FOOTER_TRAILER_LEN + block_count * 4;
+    if object.len() < footer_len {
+        // `block_count` claims a footer longer than the object: truncated,
+        // not an unrecognized format.
+        return Err(WriterError::FooterCorrupt);
+    }
+    let data_len = object.len() - footer_len;
+
+    let footer_body = &object[data_len..len - 4];
+    if CRC32C.checksum(footer_body) != stored_footer_crc {
+        return Err(WriterError::FooterCorrupt);
+    }
+
+    if verify {
+        for block_index in 0..block_count {
+            let start = block_index * BLOCK_SIZE;
+            let end = ((block_index + 1) * BLOCK_SIZE).min(data_len);
+            let crc_offset = data_len + block_index * 4;
+            let expected =
+                u32::from_be_bytes(object[crc_offset..crc_offset + 4].try_into().unwrap());
+            if CRC32C.checksum(&object[start..end]) != expected {
+                return Err(WriterError::ChecksumMismatch { block_index });
+            }
+        }
+    }
+
+    Ok(object.slice(0..data_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object_store::MemoryObjectStore;
+
+    #[tokio::test]
+    async fn write_then_read_roundtrips() {
+        let writer = Writer::new(Arc::new(MemoryObjectStore::new()));
+        let data = vec![7u8; BLOCK_SIZE * 2 + 123];
+
+        writer.write_segment("seg", &data).await.unwrap();
+        let read_back = writer.read_segment("seg").await.unwrap();
+
+        assert_eq!(read_back.as_ref(), data.as_slice());
+    }
+
+    #[tokio::test]
+    async fn corrupted_block_is_detected() {
+        let store = Arc::new(MemoryObjectStore::new());
+        let writer = Writer::new(store.clone());
+        writer.write_segment("seg", b"hello world").await.unwrap();
+
+        let mut corrupted = store.get("seg").await.unwrap().to_vec();
+        corrupted[0] ^= 0xFF;
+        store.put("seg", Bytes::from(corrupted)).await.unwrap();
+
+        let err = writer.read_segment("seg").await.unwrap_err();
+        assert!(matches!(
+            err,
+            WriterError::ChecksumMismatch { block_index: 0 }
+        ));
+    }
+
+    #[tokio::test]
+    async fn verification_can_be_disabled() {
+        let store = Arc::new(MemoryObjectStore::new());
+        let writer = Writer::with_config(
+            store.clone(),
+            WriterConfig {
+                verify_checksums: false,
+            },
+        );
+        writer.write_segment("seg", b"hello world").await.unwrap();
+
+        let mut corrupted = store.get("seg").await.unwrap().to_vec();
+        corrupted[0] ^= 0xFF;
+        store.put("seg", Bytes::from(corrupted)).await.unwrap();
+
+        let read_back = writer.read_segment("seg").await.unwrap();
+        assert_eq!(read_back.len(), b"hello world".len());
+        assert_ne!(read_back.as_ref(), b"hello world");
+    }
+
+    #[tokio::test]
+    async fn object_too_small_for_a_footer_is_reported_as_corrupt_not_unsupported() {
+        let store = Arc::new(MemoryObjectStore::new());
+        let writer = Writer::new(store.clone());
+        store.put("seg", Bytes::from_static(b"abc")).await.unwrap();
 
-    return writer
-  }
+        let err = writer.read_segment("seg").await.unwrap_err();
+        assert!(matches!(err, WriterError::FooterCorrupt));
+    }
 }
@@ -0,0 +1,59 @@
+/// Static (access key / secret key) credentials for an S3-compatible
+/// endpoint. When a [`StorageConfig`] omits these, it falls back to the
+/// default AWS credential chain (env vars, instance profile, etc.).
+#[derive(Clone)]
+pub struct StorageCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+/// Describes one named object-storage backend: a bucket on a specific
+/// endpoint (prod S3, a MinIO dev box, a GCS-over-S3 gateway, ...), plus
+/// whatever the client needs to reach it. A process can hold several of
+/// these and pick one by name at runtime instead of baking a single
+/// endpoint into how it constructs `S3ObjectStore`.
+#[derive(Clone)]
+pub struct StorageConfig {
+    pub name: String,
+    pub bucket: String,
+    pub region: String,
+    /// Overrides the default AWS endpoint, e.g. `http://localhost:9000`
+    /// for MinIO or a GCS XML API gateway URL.
+    pub endpoint_url: Option<String>,
+    /// MinIO and most self-hosted S3-compatible servers require
+    /// path-style addressing (`endpoint/bucket/key`) instead of S3's
+    /// virtual-hosted style (`bucket.endpoint/key`).
+    pub force_path_style: bool,
+    pub credentials: Option<StorageCredentials>,
+}
+
+impl StorageConfig {
+    pub fn new(
+        name: impl Into<String>,
+        bucket: impl Into<String>,
+        region: impl Into<String>,
+    ) -> Self {
+        StorageConfig {
+            name: name.into(),
+            bucket: bucket.into(),
+            region: region.into(),
+            endpoint_url: None,
+            force_path_style: false,
+            credentials: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_defaults_to_aws_endpoint_and_virtual_hosted_style() {
+        let config = StorageConfig::new("prod", "my-bucket", "us-east-1");
+        assert_eq!(config.endpoint_url, None);
+        assert!(!config.force_path_style);
+        assert!(config.credentials.is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::db::DB;
+use crate::object_store::S3ObjectStore;
+use crate::storage_config::StorageConfig;
+
+/// Holds one `DB` per named [`StorageConfig`], so a process can address
+/// several buckets or S3-compatible endpoints by name (e.g. `"prod"`,
+/// `"minio-dev"`) instead of hard-coding which one it talks to.
+pub struct StorageRegistry {
+    dbs: HashMap<String, Arc<DB<S3ObjectStore>>>,
+}
+
+impl StorageRegistry {
+    /// Connects to every backend described in `configs`, keyed by
+    /// `StorageConfig::name`.
+    pub async fn connect(configs: &[StorageConfig]) -> Self {
+        let mut dbs = HashMap::with_capacity(configs.len());
+        for config in configs {
+            let store = S3ObjectStore::from_config(config).await;
+            dbs.insert(config.name.clone(), Arc::new(DB::new(Arc::new(store))));
+        }
+        StorageRegistry { dbs }
+    }
+
+    /// Returns the `DB` registered under `name`, if any.
+    pub fn db(&self, name: &str) -> Option<&Arc<DB<S3ObjectStore>>> {
+        self.dbs.get(name)
+    }
+}
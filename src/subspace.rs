@@ -1,28 +1,63 @@
+use bytes::Bytes;
+
+use crate::causality::CausalityToken;
 use crate::db::{DBOps, DB};
+use crate::error::DbError;
+use crate::object_store::ObjectStore;
+use crate::write_batch::WriteBatch;
+
+pub struct Subspace<'a, S: ObjectStore> {
+    db: &'a DB<S>,
+    prefix: String,
+}
 
-pub struct Subspace<'a> {
-    db: &'a DB,
+impl<'a, S: ObjectStore> Subspace<'a, S> {
+    pub(crate) fn new(db: &'a DB<S>, prefix: &str) -> Self {
+        Subspace {
+            db,
+            prefix: prefix.to_string(),
+        }
+    }
 }
 
-impl DBOps for Subspace<'_> {
-    async fn get(&self, key: &str) -> Result<(), ()> {
-        todo!()
+impl<S: ObjectStore> DBOps for Subspace<'_, S> {
+    async fn get(&self, key: &str) -> Result<(Bytes, CausalityToken), DbError> {
+        self.db.get_in_subspace(&self.prefix, key).await
     }
 
-    async fn write(&self, key: &str, value: &[u8]) -> Result<(), ()> {
-        todo!()
+    async fn write(&self, key: &str, value: &[u8]) -> Result<CausalityToken, DbError> {
+        let mut batch = WriteBatch::new();
+        batch.put_in_subspace(&self.prefix, key, Bytes::copy_from_slice(value));
+        self.db.write_batch(batch).await
+    }
+
+    async fn write_if(
+        &self,
+        key: &str,
+        value: &[u8],
+        expected_token: &CausalityToken,
+    ) -> Result<CausalityToken, DbError> {
+        self.db
+            .write_if_in_subspace(&self.prefix, key, value, expected_token)
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::object_store::MemoryObjectStore;
+    use std::sync::Arc;
 
     #[tokio::test]
-    async fn get() {
-        let db = &DB {};
-        let s = Subspace { db: db };
-        let r = s.get("hey").await;
-        println!("Result: {:?}", r)
+    async fn get_put_roundtrip() {
+        let db = DB::new(Arc::new(MemoryObjectStore::new()));
+        let s = db.subspace("hey");
+
+        assert!(matches!(s.get("hey").await, Err(DbError::NotFound)));
+
+        s.write("hey", b"value").await.unwrap();
+        let (value, _) = s.get("hey").await.unwrap();
+        assert_eq!(value, Bytes::from_static(b"value"));
     }
 }
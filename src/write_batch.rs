@@ -0,0 +1,247 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// A single mutation accumulated into a [`WriteBatch`]. Visible to
+/// [`crate::db::DB`] so it can merge a batch's ops into the segment that
+/// already represents the current state, and so the read path can scan a
+/// decoded segment for the latest value of a key.
+#[derive(Clone)]
+pub(crate) enum BatchOp {
+    Put {
+        subspace: String,
+        key: String,
+        value: Bytes,
+    },
+    Delete {
+        subspace: String,
+        key: String,
+    },
+}
+
+impl BatchOp {
+    pub(crate) fn targets(&self, subspace: &str, key: &str) -> bool {
+        match self {
+            BatchOp::Put { subspace: s, key: k, .. } => s == subspace && k == key,
+            BatchOp::Delete { subspace: s, key: k } => s == subspace && k == key,
+        }
+    }
+}
+
+/// Accumulates `put`/`delete` operations, possibly across several
+/// subspaces, so they can be committed together with `DB::write_batch`.
+/// Object stores lack multi-object transactions, so the batch is merged
+/// into a single immutable segment object and published atomically via
+/// the manifest pointer; readers only ever see it as a whole.
+#[derive(Default)]
+pub struct WriteBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl WriteBatch {
+    pub fn new() -> Self {
+        WriteBatch::default()
+    }
+
+    pub fn put(&mut self, key: &str, value: impl Into<Bytes>) -> &mut Self {
+        self.put_in_subspace("", key, value)
+    }
+
+    pub fn delete(&mut self, key: &str) -> &mut Self {
+        self.delete_in_subspace("", key)
+    }
+
+    pub(crate) fn put_in_subspace(
+        &mut self,
+        subspace: &str,
+        key: &str,
+        value: impl Into<Bytes>,
+    ) -> &mut Self {
+        self.ops.push(BatchOp::Put {
+            subspace: subspace.to_string(),
+            key: key.to_string(),
+            value: value.into(),
+        });
+        self
+    }
+
+    pub(crate) fn delete_in_subspace(&mut self, subspace: &str, key: &str) -> &mut Self {
+        self.ops.push(BatchOp::Delete {
+            subspace: subspace.to_string(),
+            key: key.to_string(),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Exposes the accumulated ops without consuming the batch, so a
+    /// caller that needs to retry a commit (e.g. against a CAS conflict)
+    /// can read them again on each attempt.
+    pub(crate) fn ops(&self) -> &[BatchOp] {
+        &self.ops
+    }
+}
+
+/// Serializes `ops` into the body of a segment object: a sequence of
+/// records, one per operation, each an op tag byte followed by
+/// length-prefixed fields.
+pub(crate) fn encode_ops(ops: &[BatchOp]) -> Bytes {
+    let mut buf = BytesMut::new();
+    for op in ops {
+        match op {
+            BatchOp::Put {
+                subspace,
+                key,
+                value,
+            } => {
+                buf.put_u8(0);
+                write_field(&mut buf, subspace.as_bytes());
+                write_field(&mut buf, key.as_bytes());
+                write_field(&mut buf, value);
+            }
+            BatchOp::Delete { subspace, key } => {
+                buf.put_u8(1);
+                write_field(&mut buf, subspace.as_bytes());
+                write_field(&mut buf, key.as_bytes());
+            }
+        }
+    }
+    buf.freeze()
+}
+
+/// Errors surfaced while decoding a segment object's body back into
+/// [`BatchOp`]s. A malformed object (truncated upload, bit-rot, a segment
+/// written by an incompatible version) is reported instead of panicking,
+/// since the bytes come straight from the object store.
+#[derive(Debug)]
+pub(crate) enum DecodeError {
+    /// The buffer ended in the middle of a record.
+    UnexpectedEof,
+    /// A subspace or key field wasn't valid UTF-8.
+    InvalidUtf8,
+    /// The op tag byte didn't match any known [`BatchOp`] variant.
+    UnknownOpTag(u8),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "write-batch record truncated"),
+            DecodeError::InvalidUtf8 => write!(f, "write-batch field is not valid utf-8"),
+            DecodeError::UnknownOpTag(tag) => write!(f, "unknown write-batch op tag: {tag}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Inverse of [`encode_ops`]: decodes a segment object's body back into
+/// its sequence of operations, in the order they were applied.
+pub(crate) fn decode_ops(mut bytes: Bytes) -> Result<Vec<BatchOp>, DecodeError> {
+    let mut ops = Vec::new();
+    while bytes.has_remaining() {
+        let tag = read_u8(&mut bytes)?;
+        let subspace = read_field_string(&mut bytes)?;
+        let key = read_field_string(&mut bytes)?;
+        match tag {
+            0 => {
+                let value = read_field(&mut bytes)?;
+                ops.push(BatchOp::Put {
+                    subspace,
+                    key,
+                    value,
+                });
+            }
+            1 => ops.push(BatchOp::Delete { subspace, key }),
+            other => return Err(DecodeError::UnknownOpTag(other)),
+        }
+    }
+    Ok(ops)
+}
+
+fn write_field(buf: &mut BytesMut, field: &[u8]) {
+    buf.put_u32(field.len() as u32);
+    buf.put_slice(field);
+}
+
+fn read_u8(bytes: &mut Bytes) -> Result<u8, DecodeError> {
+    if bytes.remaining() < 1 {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    Ok(bytes.get_u8())
+}
+
+fn read_u32(bytes: &mut Bytes) -> Result<u32, DecodeError> {
+    if bytes.remaining() < 4 {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    Ok(bytes.get_u32())
+}
+
+fn read_field(bytes: &mut Bytes) -> Result<Bytes, DecodeError> {
+    let len = read_u32(bytes)? as usize;
+    if bytes.remaining() < len {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    Ok(bytes.split_to(len))
+}
+
+fn read_field_string(bytes: &mut Bytes) -> Result<String, DecodeError> {
+    String::from_utf8(read_field(bytes)?.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_is_not_empty_once_populated() {
+        let mut batch = WriteBatch::new();
+        assert!(batch.is_empty());
+
+        batch.put("key", Bytes::from_static(b"value"));
+        batch.delete_in_subspace("sub", "other-key");
+
+        assert!(!batch.is_empty());
+        assert!(!encode_ops(batch.ops()).is_empty());
+    }
+
+    #[test]
+    fn decode_reverses_encode() {
+        let mut batch = WriteBatch::new();
+        batch.put_in_subspace("a", "k1", Bytes::from_static(b"v1"));
+        batch.delete_in_subspace("b", "k2");
+
+        let encoded = encode_ops(batch.ops());
+        let decoded = decode_ops(encoded).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded[0].targets("a", "k1"));
+        assert!(decoded[1].targets("b", "k2"));
+    }
+
+    #[test]
+    fn decode_rejects_truncated_record() {
+        let mut batch = WriteBatch::new();
+        batch.put("key", Bytes::from_static(b"value"));
+
+        let mut encoded = encode_ops(batch.ops());
+        encoded.truncate(encoded.len() - 1);
+
+        assert!(matches!(decode_ops(encoded), Err(DecodeError::UnexpectedEof)));
+    }
+
+    #[test]
+    fn decode_rejects_unknown_op_tag() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(2);
+        buf.put_u32(0);
+        buf.put_u32(0);
+
+        assert!(matches!(
+            decode_ops(buf.freeze()),
+            Err(DecodeError::UnknownOpTag(2))
+        ));
+    }
+}